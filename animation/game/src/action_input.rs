@@ -0,0 +1,406 @@
+//! Logical input layer. Maps physical keyboard/mouse/gamepad sources onto
+//! named actions so gameplay code never looks at a keycode directly.
+
+use fyrox::{
+    core::{algebra::Vector2, reflect::prelude::*, visitor::prelude::*},
+    event::{DeviceEvent, ElementState, Event, WindowEvent},
+    keyboard::{KeyCode, PhysicalKey},
+};
+use std::{collections::HashMap, path::Path};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Visit, Reflect, Default)]
+pub enum MouseButtonCode {
+    #[default]
+    Left,
+    Right,
+    Middle,
+    Other(u16),
+}
+
+impl From<fyrox::event::MouseButton> for MouseButtonCode {
+    fn from(button: fyrox::event::MouseButton) -> Self {
+        match button {
+            fyrox::event::MouseButton::Left => Self::Left,
+            fyrox::event::MouseButton::Right => Self::Right,
+            fyrox::event::MouseButton::Middle => Self::Middle,
+            fyrox::event::MouseButton::Other(code) => Self::Other(code),
+            _ => Self::Other(u16::MAX),
+        }
+    }
+}
+
+/// A single physical source that can drive an action.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Visit, Reflect)]
+pub enum Binding {
+    Key(KeyCode),
+    MouseButton(MouseButtonCode),
+    MouseMotionX,
+    MouseMotionY,
+    GamepadAxis(u32),
+    GamepadButton(u32),
+}
+
+impl Default for Binding {
+    fn default() -> Self {
+        Self::Key(KeyCode::Space)
+    }
+}
+
+/// How an axis action obtains its value (see [`ActionKind::Axis`] for its
+/// range).
+#[derive(Clone, Debug, Visit, Reflect)]
+pub enum AxisSource {
+    /// Two opposed digital bindings, e.g. `W` (positive) / `S` (negative).
+    ButtonPair { positive: Binding, negative: Binding },
+    /// A single source that already produces a continuous value, e.g. a
+    /// gamepad stick axis or raw mouse motion.
+    Analog(Binding),
+}
+
+#[derive(Clone, Debug, Visit, Reflect)]
+pub enum ActionKind {
+    /// Reports a 0/1 pressed state.
+    Button { bindings: Vec<Binding> },
+    /// Reports a continuous value: in `[-1, 1]` for button pairs and gamepad
+    /// axes, while mouse motion reports the frame's accumulated raw delta,
+    /// which is not clamped.
+    Axis { source: AxisSource },
+}
+
+#[derive(Clone, Debug, Visit, Reflect)]
+pub struct ActionDef {
+    pub name: String,
+    pub kind: ActionKind,
+}
+
+#[derive(Clone, Debug, Default, Visit, Reflect)]
+pub struct Layout {
+    pub name: String,
+    pub actions: Vec<ActionDef>,
+}
+
+impl Layout {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            actions: Vec::new(),
+        }
+    }
+
+    pub fn with_button(mut self, name: impl Into<String>, bindings: Vec<Binding>) -> Self {
+        self.actions.push(ActionDef {
+            name: name.into(),
+            kind: ActionKind::Button { bindings },
+        });
+        self
+    }
+
+    pub fn with_axis(mut self, name: impl Into<String>, source: AxisSource) -> Self {
+        self.actions.push(ActionDef {
+            name: name.into(),
+            kind: ActionKind::Axis { source },
+        });
+        self
+    }
+}
+
+/// Identifies which binding of an action [`ActionHandler::rebind`] should
+/// overwrite.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BindingSlot {
+    /// The `index`-th binding of a `Button` action.
+    Button(usize),
+    AxisPositive,
+    AxisNegative,
+    AxisAnalog,
+}
+
+/// Builds an [`ActionHandler`] out of one or more named [`Layout`]s.
+#[derive(Default)]
+pub struct ActionHandlerBuilder {
+    layouts: Vec<Layout>,
+    active_layout: Option<String>,
+}
+
+impl ActionHandlerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_layout(mut self, layout: Layout) -> Self {
+        if self.active_layout.is_none() {
+            self.active_layout = Some(layout.name.clone());
+        }
+        self.layouts.push(layout);
+        self
+    }
+
+    pub fn with_active_layout(mut self, name: impl Into<String>) -> Self {
+        self.active_layout = Some(name.into());
+        self
+    }
+
+    pub fn build(self) -> ActionHandler {
+        let active_layout = self.active_layout.unwrap_or_default();
+        ActionHandler {
+            layouts: self
+                .layouts
+                .into_iter()
+                .map(|layout| (layout.name.clone(), layout))
+                .collect(),
+            active_layout,
+            digital_state: HashMap::new(),
+            mouse_delta: Vector2::default(),
+            gamepad_axes: HashMap::new(),
+        }
+    }
+}
+
+/// Turns OS input events into named action values, decoupled from the
+/// physical bindings that produce them. Feed it events from
+/// [`fyrox::plugin::Plugin::on_os_event`] and query it from script code via
+/// [`ActionHandler::action_value`] / [`ActionHandler::is_pressed`].
+#[derive(Visit, Reflect, Debug)]
+pub struct ActionHandler {
+    layouts: HashMap<String, Layout>,
+    active_layout: String,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    digital_state: HashMap<Binding, bool>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    mouse_delta: Vector2<f32>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    gamepad_axes: HashMap<u32, f32>,
+}
+
+impl ActionHandler {
+    pub fn builder() -> ActionHandlerBuilder {
+        ActionHandlerBuilder::new()
+    }
+
+    pub fn set_active_layout(&mut self, name: &str) {
+        if self.layouts.contains_key(name) {
+            self.active_layout = name.to_string();
+        }
+    }
+
+    pub fn active_layout(&self) -> &str {
+        &self.active_layout
+    }
+
+    /// Overwrites a single binding of `action` in `layout`, so a remapping
+    /// UI can let users change controls at runtime. Returns `false` if the
+    /// layout/action/slot combination doesn't exist.
+    pub fn rebind(
+        &mut self,
+        layout: &str,
+        action: &str,
+        slot: BindingSlot,
+        binding: Binding,
+    ) -> bool {
+        let Some(layout) = self.layouts.get_mut(layout) else {
+            return false;
+        };
+        let Some(action) = layout.actions.iter_mut().find(|a| a.name == action) else {
+            return false;
+        };
+
+        match (&mut action.kind, slot) {
+            (ActionKind::Button { bindings }, BindingSlot::Button(index)) => {
+                match bindings.get_mut(index) {
+                    Some(slot) => {
+                        *slot = binding;
+                        true
+                    }
+                    None => false,
+                }
+            }
+            (
+                ActionKind::Axis {
+                    source: AxisSource::ButtonPair { positive, .. },
+                },
+                BindingSlot::AxisPositive,
+            ) => {
+                *positive = binding;
+                true
+            }
+            (
+                ActionKind::Axis {
+                    source: AxisSource::ButtonPair { negative, .. },
+                },
+                BindingSlot::AxisNegative,
+            ) => {
+                *negative = binding;
+                true
+            }
+            (
+                ActionKind::Axis {
+                    source: AxisSource::Analog(slot_binding),
+                },
+                BindingSlot::AxisAnalog,
+            ) => {
+                *slot_binding = binding;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Loads a previously-saved binding set written by
+    /// [`ActionHandler::save_bindings`], so users can remap controls across
+    /// sessions.
+    pub fn load_bindings(path: &Path) -> Option<Self> {
+        let mut visitor = Visitor::load_binary(path).ok()?;
+        let mut handler = Self::default();
+        handler.visit("ActionHandler", &mut visitor).ok()?;
+        Some(handler)
+    }
+
+    /// Returns a handler loaded from `path` if it exists and is valid,
+    /// otherwise falls back to `default`.
+    pub fn load_or_build(path: &Path, default: impl FnOnce() -> Self) -> Self {
+        if path.exists() {
+            if let Some(handler) = Self::load_bindings(path) {
+                return handler;
+            }
+        }
+        default()
+    }
+
+    /// Persists the current layouts/bindings (not the live per-frame input
+    /// state) to `path`.
+    pub fn save_bindings(&self, path: &Path) -> std::io::Result<()> {
+        let mut visitor = Visitor::new();
+        let mut settings = ActionHandler {
+            layouts: self.layouts.clone(),
+            active_layout: self.active_layout.clone(),
+            digital_state: HashMap::new(),
+            mouse_delta: Vector2::default(),
+            gamepad_axes: HashMap::new(),
+        };
+        settings.visit("ActionHandler", &mut visitor)?;
+        visitor.save_binary(path)
+    }
+
+    /// Feeds a raw OS event into the handler. Call this from
+    /// `Plugin::on_os_event`.
+    pub fn process_os_event(&mut self, event: &Event<()>) {
+        match event {
+            Event::WindowEvent { event, .. } => {
+                if let WindowEvent::KeyboardInput { event, .. } = event {
+                    if let PhysicalKey::Code(key_code) = event.physical_key {
+                        self.digital_state
+                            .insert(Binding::Key(key_code), event.state == ElementState::Pressed);
+                    }
+                } else if let WindowEvent::MouseInput { state, button, .. } = event {
+                    self.digital_state.insert(
+                        Binding::MouseButton((*button).into()),
+                        *state == ElementState::Pressed,
+                    );
+                }
+            }
+            Event::DeviceEvent { event, .. } => {
+                if let DeviceEvent::MouseMotion { delta } = event {
+                    self.mouse_delta.x += delta.0 as f32;
+                    self.mouse_delta.y += delta.1 as f32;
+                }
+            }
+            _ => (),
+        }
+    }
+
+    pub fn set_gamepad_axis(&mut self, axis: u32, value: f32) {
+        self.gamepad_axes.insert(axis, value.clamp(-1.0, 1.0));
+    }
+
+    pub fn set_gamepad_button(&mut self, button: u32, pressed: bool) {
+        self.digital_state
+            .insert(Binding::GamepadButton(button), pressed);
+    }
+
+    /// Clears the per-frame accumulators (mouse motion). Call this once per
+    /// frame after gameplay code has consumed the current values, e.g. at
+    /// the end of [`fyrox::plugin::Plugin::update`].
+    pub fn end_frame(&mut self) {
+        self.mouse_delta = Vector2::default();
+    }
+
+    fn binding_value(&self, binding: &Binding) -> f32 {
+        match binding {
+            Binding::MouseMotionX => self.mouse_delta.x,
+            Binding::MouseMotionY => self.mouse_delta.y,
+            Binding::GamepadAxis(axis) => self.gamepad_axes.get(axis).copied().unwrap_or(0.0),
+            other => self
+                .digital_state
+                .get(other)
+                .copied()
+                .unwrap_or(false)
+                .then_some(1.0)
+                .unwrap_or(0.0),
+        }
+    }
+
+    fn find_action(&self, name: &str) -> Option<&ActionDef> {
+        self.layouts
+            .get(&self.active_layout)?
+            .actions
+            .iter()
+            .find(|action| action.name == name)
+    }
+
+    /// Returns the current value of an action: 0 or 1 for a button, in
+    /// `[-1, 1]` for a button-pair or gamepad axis, and the unclamped
+    /// accumulated mouse delta of the frame for a mouse motion axis.
+    pub fn action_value(&self, name: &str) -> f32 {
+        let Some(action) = self.find_action(name) else {
+            return 0.0;
+        };
+        match &action.kind {
+            ActionKind::Button { bindings } => bindings
+                .iter()
+                .any(|binding| self.binding_value(binding) > 0.5)
+                .then_some(1.0)
+                .unwrap_or(0.0),
+            ActionKind::Axis { source } => match source {
+                AxisSource::ButtonPair { positive, negative } => {
+                    self.binding_value(positive) - self.binding_value(negative)
+                }
+                AxisSource::Analog(binding) => self.binding_value(binding),
+            },
+        }
+    }
+
+    /// Returns whether a button action is currently held down.
+    pub fn is_pressed(&self, name: &str) -> bool {
+        self.action_value(name) > 0.5
+    }
+}
+
+impl Default for ActionHandler {
+    fn default() -> Self {
+        ActionHandlerBuilder::new().build()
+    }
+}
+
+pub fn default_gameplay_layout() -> Layout {
+    Layout::new("Gameplay")
+        .with_axis(
+            "MoveForward",
+            AxisSource::ButtonPair {
+                positive: Binding::Key(KeyCode::KeyW),
+                negative: Binding::Key(KeyCode::KeyS),
+            },
+        )
+        .with_axis(
+            "MoveRight",
+            AxisSource::ButtonPair {
+                positive: Binding::Key(KeyCode::KeyD),
+                negative: Binding::Key(KeyCode::KeyA),
+            },
+        )
+        .with_axis("LookYaw", AxisSource::Analog(Binding::MouseMotionX))
+        .with_axis("LookPitch", AxisSource::Analog(Binding::MouseMotionY))
+        .with_button("Jump", vec![Binding::Key(KeyCode::Space)])
+}
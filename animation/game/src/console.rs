@@ -0,0 +1,188 @@
+//! In-game developer console. Subscribes to `fyrox::core::log::Log` and
+//! streams every message into a scrollable panel, mirroring the way the
+//! editor's build window drains a subprocess's output into a text widget.
+
+use fyrox::{
+    core::log::{Log, MessageKind},
+    gui::{
+        brush::Brush,
+        button::{ButtonBuilder, ButtonMessage},
+        check_box::{CheckBoxBuilder, CheckBoxMessage},
+        message::MessageDirection,
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        text::TextBuilder,
+        widget::{WidgetBuilder, WidgetMessage},
+        BuildContext, Thickness, UiNode, UserInterface,
+    },
+};
+use fyrox::core::pool::Handle;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+const MAX_LINES: usize = 500;
+
+struct LogLine {
+    kind: MessageKind,
+    text: String,
+}
+
+fn color_for(kind: MessageKind) -> Brush {
+    match kind {
+        MessageKind::Error => Brush::Solid(fyrox::core::color::Color::RED),
+        MessageKind::Warning => Brush::Solid(fyrox::core::color::Color::from_rgba(230, 200, 60, 255)),
+        MessageKind::Information => Brush::Solid(fyrox::core::color::Color::WHITE),
+    }
+}
+
+/// Scrollable overlay that mirrors everything logged through [`Log`].
+pub struct DevConsole {
+    panel: Handle<UiNode>,
+    scroll_viewer: Handle<UiNode>,
+    log_list: Handle<UiNode>,
+    errors_only: Handle<UiNode>,
+    copy_button: Handle<UiNode>,
+    receiver: Receiver<(MessageKind, String)>,
+    lines: Vec<LogLine>,
+    show_errors_only: bool,
+}
+
+// Bridges `Log`'s callback-style listener onto a channel we can drain from
+// `Game::update`, the same shape the editor uses for subprocess output.
+struct ChannelLogListener(Sender<(MessageKind, String)>);
+
+impl fyrox::core::log::LogListener for ChannelLogListener {
+    fn on_message(&mut self, kind: MessageKind, message: &str) {
+        let _ = self.0.send((kind, message.to_string()));
+    }
+}
+
+impl DevConsole {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Log::add_listener(Box::new(ChannelLogListener(sender)));
+
+        let log_list = StackPanelBuilder::new(WidgetBuilder::new()).build(ctx);
+
+        let scroll_viewer = ScrollViewerBuilder::new(
+            WidgetBuilder::new()
+                .with_height(220.0)
+                .with_margin(Thickness::uniform(2.0)),
+        )
+        .with_content(log_list)
+        .build(ctx);
+
+        let errors_only = CheckBoxBuilder::new(WidgetBuilder::new().with_width(20.0).with_height(20.0))
+            .with_content(
+                TextBuilder::new(WidgetBuilder::new())
+                    .with_text("Errors/warnings only")
+                    .build(ctx),
+            )
+            .checked(Some(false))
+            .build(ctx);
+
+        let copy_button = ButtonBuilder::new(WidgetBuilder::new().with_width(100.0).with_height(22.0))
+            .with_text("Copy")
+            .build(ctx);
+
+        let panel = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(false)
+                .with_margin(Thickness::uniform(4.0))
+                .with_child(scroll_viewer)
+                .with_child(errors_only)
+                .with_child(copy_button),
+        )
+        .build(ctx);
+
+        Self {
+            panel,
+            scroll_viewer,
+            log_list,
+            errors_only,
+            copy_button,
+            receiver,
+            lines: Vec::new(),
+            show_errors_only: false,
+        }
+    }
+
+    pub fn toggle(&self, ui: &UserInterface) {
+        let visible = !ui.node(self.panel).is_globally_visible();
+        ui.send_message(WidgetMessage::visibility(
+            self.panel,
+            MessageDirection::ToWidget,
+            visible,
+        ));
+    }
+
+    fn rebuild_visible_lines(&self, ui: &mut UserInterface) {
+        for child in ui.node(self.log_list).children().to_vec() {
+            ui.send_message(WidgetMessage::remove(child, MessageDirection::ToWidget));
+        }
+
+        for line in &self.lines {
+            if self.show_errors_only && line.kind == MessageKind::Information {
+                continue;
+            }
+
+            let ctx = &mut ui.build_ctx();
+            let text = TextBuilder::new(WidgetBuilder::new().with_foreground(color_for(line.kind)))
+                .with_text(line.text.clone())
+                .build(ctx);
+            ui.send_message(WidgetMessage::link(
+                text,
+                MessageDirection::ToWidget,
+                self.log_list,
+            ));
+        }
+
+        ui.send_message(WidgetMessage::desired_position(
+            self.scroll_viewer,
+            MessageDirection::ToWidget,
+            fyrox::core::algebra::Vector2::new(0.0, f32::MAX),
+        ));
+    }
+
+    /// Drains pending log messages arriving from the [`Log`] listener. Call
+    /// once per frame from `Game::update`.
+    pub fn drain_log(&mut self, ui: &mut UserInterface) {
+        let mut changed = false;
+        while let Ok((kind, text)) = self.receiver.try_recv() {
+            self.lines.push(LogLine { kind, text });
+            if self.lines.len() > MAX_LINES {
+                self.lines.remove(0);
+            }
+            changed = true;
+        }
+
+        if changed {
+            self.rebuild_visible_lines(ui);
+        }
+    }
+
+    /// Handles a UI message pulled from the shared `Game` message pump.
+    /// Returns `true` if the message was meant for the console.
+    pub fn handle_ui_message(&mut self, ui: &mut UserInterface, message: &fyrox::gui::message::UiMessage) -> bool {
+        if let Some(CheckBoxMessage::Check(value)) = message.data() {
+            if message.destination() == self.errors_only {
+                self.show_errors_only = value.unwrap_or(false);
+                self.rebuild_visible_lines(ui);
+                return true;
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.copy_button {
+                let text = self
+                    .lines
+                    .iter()
+                    .map(|line| line.text.as_str())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                if let Some(clipboard) = ui.clipboard_mut() {
+                    fyrox::core::log::Log::verify(clipboard.set_text(text));
+                }
+                return true;
+            }
+        }
+        false
+    }
+}
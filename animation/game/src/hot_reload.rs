@@ -0,0 +1,156 @@
+//! Watches `data/scene.rgs` and the texture assets under `data/textures` for
+//! filesystem changes and drives a background reload while the game runs.
+
+use crate::player::Player;
+use fyrox::{
+    core::{algebra::Vector3, log::Log},
+    engine::{ResourceManager, SerializationContext},
+    scene::{loader::AsyncSceneLoader, rigidbody::RigidBody, Scene},
+};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+
+/// Minimum time between recursive filesystem walks of `textures_path`, so
+/// `poll_changed` doesn't do a blocking `read_dir`/`metadata` stat walk on
+/// the game thread every single frame.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+fn newest_mtime(path: &Path) -> Option<SystemTime> {
+    if path.is_dir() {
+        std::fs::read_dir(path)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| newest_mtime(&entry.path()))
+            .max()
+    } else {
+        std::fs::metadata(path).ok()?.modified().ok()
+    }
+}
+
+/// Transient runtime state that should survive a hot-reload swap.
+pub struct PlayerState {
+    /// Local-space position, i.e. relative to the player node's parent -
+    /// matching the space `restore_player_state` writes back into.
+    pub position: Vector3<f32>,
+    pub velocity: Vector3<f32>,
+    /// Camera look rotation, since a freshly deserialized `Player` always
+    /// resets it to `(0.0, 0.0)`.
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+pub fn capture_player_state(scene: &Scene) -> Option<PlayerState> {
+    for node in scene.graph.linear_iter() {
+        if let Some(player) = node.try_get_script::<Player>() {
+            let velocity = node
+                .cast::<RigidBody>()
+                .map(RigidBody::lin_vel)
+                .unwrap_or_default();
+            let (yaw, pitch) = player.look_angles();
+            return Some(PlayerState {
+                position: *node.local_transform().position(),
+                velocity,
+                yaw,
+                pitch,
+            });
+        }
+    }
+    None
+}
+
+pub fn restore_player_state(scene: &mut Scene, state: &PlayerState) {
+    let Some(player_handle) = scene
+        .graph
+        .pair_iter()
+        .find(|(_, node)| node.try_get_script::<Player>().is_some())
+        .map(|(handle, _)| handle)
+    else {
+        return;
+    };
+
+    let node = &mut scene.graph[player_handle];
+    node.local_transform_mut().set_position(state.position);
+    if let Some(rigid_body) = node.cast_mut::<RigidBody>() {
+        rigid_body.set_lin_vel(state.velocity);
+    }
+
+    if let Some(player) = node.try_get_script_mut::<Player>() {
+        player.set_look_angles(state.yaw, state.pitch);
+    }
+}
+
+pub struct HotReloadWatcher {
+    enabled: bool,
+    scene_path: PathBuf,
+    textures_path: PathBuf,
+    last_seen: Option<SystemTime>,
+    last_poll: Instant,
+}
+
+impl HotReloadWatcher {
+    pub fn new(scene_path: impl Into<PathBuf>, textures_path: impl Into<PathBuf>) -> Self {
+        let scene_path = scene_path.into();
+        let textures_path = textures_path.into();
+        let last_seen = Self::combined_mtime(&scene_path, &textures_path);
+        Self {
+            // Disabled by default in release builds; can be toggled at
+            // runtime with F5.
+            enabled: cfg!(debug_assertions),
+            scene_path,
+            textures_path,
+            last_seen,
+            last_poll: Instant::now(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    fn combined_mtime(scene_path: &Path, textures_path: &Path) -> Option<SystemTime> {
+        newest_mtime(scene_path)
+            .into_iter()
+            .chain(newest_mtime(textures_path))
+            .max()
+    }
+
+    /// Returns `true` once if the watched assets changed since the last
+    /// walk. Safe to call every frame: the actual recursive stat walk is
+    /// throttled to [`POLL_INTERVAL`] so it doesn't block the game thread
+    /// on every update.
+    pub fn poll_changed(&mut self) -> bool {
+        if !self.enabled {
+            return false;
+        }
+
+        let now = Instant::now();
+        if now.duration_since(self.last_poll) < POLL_INTERVAL {
+            return false;
+        }
+        self.last_poll = now;
+
+        let current = Self::combined_mtime(&self.scene_path, &self.textures_path);
+        if current > self.last_seen {
+            self.last_seen = current;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn begin_reload(
+        &self,
+        serialization_context: Arc<SerializationContext>,
+        resource_manager: ResourceManager,
+    ) -> AsyncSceneLoader {
+        Log::info(format!("Hot-reloading {}...", self.scene_path.display()));
+        AsyncSceneLoader::begin_loading(self.scene_path.clone(), serialization_context, resource_manager)
+    }
+}
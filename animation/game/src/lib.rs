@@ -3,24 +3,52 @@ use crate::player::Player;
 use fyrox::{
     core::{algebra::Vector2, log::Log, pool::Handle},
     engine::GraphicsContext,
-    event::{Event, WindowEvent},
+    event::{ElementState, Event, WindowEvent},
     event_loop::ControlFlow,
+    keyboard::{KeyCode, PhysicalKey},
     gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        decorator::DecoratorBuilder,
+        dropdown_list::{DropdownListBuilder, DropdownListMessage},
         grid::{Column, GridBuilder, Row},
         message::MessageDirection,
         progress_bar::{ProgressBarBuilder, ProgressBarMessage},
         stack_panel::StackPanelBuilder,
         text::{TextBuilder, TextMessage},
         widget::{WidgetBuilder, WidgetMessage},
-        HorizontalAlignment, Thickness, UiNode, VerticalAlignment,
+        BuildContext, HorizontalAlignment, Thickness, UiNode, VerticalAlignment,
     },
     plugin::{Plugin, PluginConstructor, PluginContext, PluginRegistrationContext},
     renderer::QualitySettings,
     resource::texture::{loader::TextureLoader, CompressionOptions, TextureImportOptions},
     scene::{loader::AsyncSceneLoader, Scene},
 };
+use std::path::Path;
 
+mod action_input;
+mod console;
+mod hot_reload;
 mod player;
+mod shadow_settings;
+mod ui_script;
+mod vfs;
+
+use crate::action_input::{default_gameplay_layout, ActionHandler, Binding, BindingSlot};
+use crate::console::DevConsole;
+use crate::hot_reload::{capture_player_state, restore_player_state, HotReloadWatcher, PlayerState};
+use crate::shadow_settings::{ShadowFilterMode, ShadowSettings};
+use crate::ui_script::UiScript;
+use crate::vfs::default_mounts;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const SHADOW_SETTINGS_PATH: &str = "data/shadow_settings.bin";
+const ACTION_BINDINGS_PATH: &str = "data/input_bindings.bin";
+const SHADOW_FILTER_MODES: [ShadowFilterMode; 3] = [
+    ShadowFilterMode::HardwarePcf,
+    ShadowFilterMode::SoftPcf,
+    ShadowFilterMode::Pcss,
+];
 
 pub struct GameConstructor;
 
@@ -47,10 +75,30 @@ pub struct Game {
     progress_bar: Handle<UiNode>,
     overlay_grid: Handle<UiNode>,
     debug_text: Handle<UiNode>,
+    pub action_handler: ActionHandler,
+    shadow_settings: ShadowSettings,
+    settings_button: Handle<UiNode>,
+    settings_panel: Handle<UiNode>,
+    shadow_mode_list: Handle<UiNode>,
+    hot_reload: HotReloadWatcher,
+    pending_player_state: Option<PlayerState>,
+    reloading_old_scene: Option<Handle<Scene>>,
+    reload_status: Option<String>,
+    console: DevConsole,
+    ui_script: Option<UiScript>,
+    ui_named: HashMap<String, Handle<UiNode>>,
 }
 
 impl Game {
     pub fn new(override_scene: Handle<Scene>, context: PluginContext) -> Self {
+        // Route every resource read (the scene and the textures it
+        // references) through the mount table so the demo can ship as a
+        // single packed archive and still work loose during development.
+        context
+            .resource_manager
+            .state()
+            .set_resource_io(Arc::new(default_mounts()));
+
         context
             .resource_manager
             .state()
@@ -74,6 +122,101 @@ impl Game {
         };
 
         let ctx = &mut context.user_interface.build_ctx();
+
+        let ui_script = UiScript::load(Path::new("data/ui.rhai"));
+        let scripted_layout = ui_script.as_ref().and_then(|script| script.build(ctx));
+
+        let (overlay_grid, progress_bar, debug_text) = if let Some(layout) = &scripted_layout {
+            (
+                layout.named.get("overlay_grid").copied().unwrap_or(layout.root),
+                layout.named.get("progress_bar").copied().unwrap_or_default(),
+                layout.named.get("debug_text").copied().unwrap_or_default(),
+            )
+        } else {
+            Self::build_default_overlay(ctx)
+        };
+        let ui_named = scripted_layout.map(|layout| layout.named).unwrap_or_default();
+
+        let shadow_settings = ShadowSettings::load_or_default(Path::new(SHADOW_SETTINGS_PATH));
+
+        let shadow_mode_list = DropdownListBuilder::new(WidgetBuilder::new().with_width(150.0))
+            .with_items(
+                SHADOW_FILTER_MODES
+                    .iter()
+                    .map(|mode| {
+                        DecoratorBuilder::new(
+                            TextBuilder::new(WidgetBuilder::new())
+                                .with_text(format!("{:?}", mode))
+                                .build(ctx),
+                        )
+                        .build(ctx)
+                    })
+                    .collect(),
+            )
+            .with_selected(
+                SHADOW_FILTER_MODES
+                    .iter()
+                    .position(|mode| *mode == shadow_settings.filter_mode)
+                    .unwrap_or(0),
+            )
+            .build(ctx);
+
+        let settings_panel = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(false)
+                .with_margin(Thickness::uniform(4.0))
+                .with_child(
+                    TextBuilder::new(WidgetBuilder::new())
+                        .with_text("Shadow filtering")
+                        .build(ctx),
+                )
+                .with_child(shadow_mode_list),
+        )
+        .build(ctx);
+
+        let settings_button = ButtonBuilder::new(
+            WidgetBuilder::new()
+                .with_horizontal_alignment(HorizontalAlignment::Right)
+                .with_width(80.0)
+                .with_height(24.0),
+        )
+        .with_text("Settings")
+        .build(ctx);
+
+        let console = DevConsole::new(ctx);
+
+        let action_handler = ActionHandler::load_or_build(Path::new(ACTION_BINDINGS_PATH), || {
+            ActionHandler::builder()
+                .with_layout(default_gameplay_layout())
+                .build()
+        });
+
+        Self {
+            scene,
+            loader,
+            progress_bar,
+            overlay_grid,
+            debug_text,
+            action_handler,
+            shadow_settings,
+            settings_button,
+            settings_panel,
+            shadow_mode_list,
+            hot_reload: HotReloadWatcher::new("data/scene.rgs", "data/textures"),
+            pending_player_state: None,
+            reloading_old_scene: None,
+            reload_status: None,
+            console,
+            ui_script,
+            ui_named,
+        }
+    }
+
+    /// Hardcoded loading overlay, used when `data/ui.rhai` is absent, fails
+    /// to compile, or its `build()` function fails.
+    fn build_default_overlay(
+        ctx: &mut BuildContext,
+    ) -> (Handle<UiNode>, Handle<UiNode>, Handle<UiNode>) {
         let progress_bar;
         let overlay_grid = GridBuilder::new(
             WidgetBuilder::new().with_child(
@@ -111,13 +254,7 @@ impl Game {
 
         let debug_text = TextBuilder::new(WidgetBuilder::new()).build(ctx);
 
-        Self {
-            scene,
-            loader,
-            progress_bar,
-            overlay_grid,
-            debug_text,
-        }
+        (overlay_grid, progress_bar, debug_text)
     }
 
     fn handle_resize(&self, context: &mut PluginContext, new_size: Vector2<f32>) {
@@ -132,16 +269,53 @@ impl Game {
             new_size.y,
         ));
     }
+
+    /// Remaps a single control and persists the new binding to
+    /// [`ACTION_BINDINGS_PATH`] so it survives a restart.
+    pub fn rebind_action(
+        &mut self,
+        layout: &str,
+        action: &str,
+        slot: BindingSlot,
+        binding: Binding,
+    ) -> bool {
+        let rebound = self.action_handler.rebind(layout, action, slot, binding);
+        if rebound {
+            Log::verify(
+                self.action_handler
+                    .save_bindings(Path::new(ACTION_BINDINGS_PATH)),
+            );
+        }
+        rebound
+    }
 }
 
 impl Plugin for Game {
     fn update(&mut self, context: &mut PluginContext, _control_flow: &mut ControlFlow) {
-        if let Some(loader) = self.loader.as_ref() {
-            if let Some(result) = loader.fetch_result() {
-                match result {
-                    Ok(scene) => {
-                        self.scene = context.scenes.add(scene);
+        if self.loader.is_none() && self.scene.is_some() && self.hot_reload.poll_changed() {
+            self.pending_player_state = capture_player_state(&context.scenes[self.scene]);
+            self.reloading_old_scene = Some(self.scene);
+            self.reload_status = Some("Hot-reloading scene...".to_string());
+            self.loader = Some(self.hot_reload.begin_reload(
+                context.serialization_context.clone(),
+                context.resource_manager.clone(),
+            ));
+        }
+
+        if let Some(result) = self.loader.as_ref().and_then(|loader| loader.fetch_result()) {
+            self.loader = None;
 
+            match result {
+                Ok(scene) => {
+                    let new_scene = context.scenes.add(scene);
+
+                    if let Some(old_scene) = self.reloading_old_scene.take() {
+                        context.scenes.remove(old_scene);
+                        if let Some(state) = self.pending_player_state.take() {
+                            restore_player_state(&mut context.scenes[new_scene], &state);
+                        }
+                        self.reload_status = Some("Scene reloaded.".to_string());
+                    } else {
                         context
                             .user_interface
                             .send_message(WidgetMessage::visibility(
@@ -150,7 +324,14 @@ impl Plugin for Game {
                                 false,
                             ));
                     }
-                    Err(err) => Log::err(err),
+
+                    self.scene = new_scene;
+                }
+                Err(err) => {
+                    self.reloading_old_scene = None;
+                    self.pending_player_state = None;
+                    self.reload_status = Some(format!("Reload failed: {err}"));
+                    Log::err(err);
                 }
             }
         }
@@ -164,13 +345,78 @@ impl Plugin for Game {
                 progress,
             ));
 
+        // Let the loading screen's script, if any, render its own label for
+        // the current progress (e.g. "42%") without a rebuild.
+        if let Some(script) = &self.ui_script {
+            if let Some(label) = self.ui_named.get("progress_label") {
+                if let Some(text) = script.format_update("progress_bar", progress as f64) {
+                    context.user_interface.send_message(TextMessage::text(
+                        *label,
+                        MessageDirection::ToWidget,
+                        text,
+                    ));
+                }
+            }
+        }
+
         if let GraphicsContext::Initialized(graphics_context) = context.graphics_context {
+            let mut text = format!("{}", graphics_context.renderer.get_statistics());
+            if let Some(status) = &self.reload_status {
+                text.push('\n');
+                text.push_str(status);
+            }
             context.user_interface.send_message(TextMessage::text(
                 self.debug_text,
                 MessageDirection::ToWidget,
-                format!("{}", graphics_context.renderer.get_statistics()),
+                text,
             ))
         }
+
+        self.action_handler.end_frame();
+        self.console.drain_log(context.user_interface);
+
+        while let Some(message) = context.user_interface.poll_message() {
+            if self
+                .console
+                .handle_ui_message(context.user_interface, &message)
+            {
+                continue;
+            }
+
+            if let Some(ButtonMessage::Click) = message.data() {
+                if message.destination() == self.settings_button {
+                    let visible = !context.user_interface.node(self.settings_panel).is_globally_visible();
+                    context
+                        .user_interface
+                        .send_message(WidgetMessage::visibility(
+                            self.settings_panel,
+                            MessageDirection::ToWidget,
+                            visible,
+                        ));
+                }
+            } else if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data()
+            {
+                if message.destination() == self.shadow_mode_list {
+                    if let Some(mode) = SHADOW_FILTER_MODES.get(*index) {
+                        self.shadow_settings.filter_mode = *mode;
+                        Log::verify(self.shadow_settings.save(Path::new(SHADOW_SETTINGS_PATH)));
+
+                        if let GraphicsContext::Initialized(graphics_context) =
+                            context.graphics_context
+                        {
+                            let mut quality_settings =
+                                graphics_context.renderer.get_quality_settings();
+                            self.shadow_settings.apply(&mut quality_settings);
+                            Log::verify(
+                                graphics_context
+                                    .renderer
+                                    .set_quality_settings(&quality_settings),
+                            );
+                        }
+                    }
+                }
+            }
+        }
     }
 
     fn on_os_event(
@@ -179,17 +425,55 @@ impl Plugin for Game {
         mut context: PluginContext,
         _control_flow: &mut ControlFlow,
     ) {
-        match event {
-            Event::WindowEvent { event, .. } => {
-                if let WindowEvent::Resized(size) = event {
-                    self.handle_resize(
-                        &mut context,
-                        Vector2::new(size.width as f32, size.height as f32),
-                    )
+        if let Event::WindowEvent { event, .. } = event {
+            if let WindowEvent::Resized(size) = event {
+                self.handle_resize(
+                    &mut context,
+                    Vector2::new(size.width as f32, size.height as f32),
+                )
+            } else if let WindowEvent::KeyboardInput {
+                event: key_event, ..
+            } = event
+            {
+                if key_event.physical_key == PhysicalKey::Code(KeyCode::F5)
+                    && key_event.state == ElementState::Pressed
+                    && !key_event.repeat
+                {
+                    let enabled = !self.hot_reload.is_enabled();
+                    self.hot_reload.set_enabled(enabled);
+                    self.reload_status = Some(format!(
+                        "Hot-reload {}",
+                        if enabled { "enabled" } else { "disabled" }
+                    ));
+                } else if key_event.physical_key == PhysicalKey::Code(KeyCode::Backquote)
+                    && key_event.state == ElementState::Pressed
+                    && !key_event.repeat
+                {
+                    self.console.toggle(context.user_interface);
                 }
             }
-            _ => (),
+        } else if matches!(event, Event::Suspended) {
+            // The OS (Android) is about to destroy the GPU surface; the
+            // engine drops `GraphicsContext::Initialized` around this event,
+            // so just make sure nothing is left expecting it to still be
+            // there.
+            Log::info("Application suspended, GPU resources are being released.".to_string());
+            context
+                .user_interface
+                .send_message(WidgetMessage::visibility(
+                    self.overlay_grid,
+                    MessageDirection::ToWidget,
+                    false,
+                ));
+        } else if matches!(event, Event::Resumed) {
+            // On desktop/WASM the surface survives and there is nothing to
+            // redo. On Android the engine recreates the surface here, which
+            // triggers `on_graphics_context_initialized` again and that
+            // re-applies quality settings and `handle_resize` for us.
+            Log::info("Application resumed.".to_string());
         }
+
+        self.action_handler.process_os_event(event);
     }
 
     fn on_graphics_context_initialized(
@@ -204,6 +488,8 @@ impl Plugin for Game {
         quality_settings.point_shadows_distance = 6.0;
         quality_settings.spot_shadows_distance = 6.0;
 
+        self.shadow_settings.apply(&mut quality_settings);
+
         Log::verify(
             graphics_context
                 .renderer
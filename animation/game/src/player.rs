@@ -0,0 +1,109 @@
+//! Player character controller, driven by the [`crate::action_input::ActionHandler`]
+//! rather than raw keycodes.
+
+use fyrox::{
+    core::{
+        algebra::{UnitQuaternion, Vector3},
+        pool::Handle,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    scene::{node::Node, rigidbody::RigidBody},
+    script::{ScriptContext, ScriptTrait},
+};
+
+use crate::Game;
+
+#[derive(Visit, Reflect, Debug, Clone)]
+pub struct Player {
+    pub camera: Handle<Node>,
+    pub move_speed: f32,
+    pub jump_speed: f32,
+    pub look_sensitivity: f32,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    yaw: f32,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pitch: f32,
+    /// Whether `Jump` was held last frame, so `on_update` can fire the
+    /// impulse once on the rising edge instead of every frame it's held.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    jump_was_pressed: bool,
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Self {
+            camera: Default::default(),
+            move_speed: 4.0,
+            jump_speed: 6.0,
+            look_sensitivity: 0.003,
+            yaw: 0.0,
+            pitch: 0.0,
+            jump_was_pressed: false,
+        }
+    }
+}
+
+impl TypeUuidProvider for Player {
+    fn type_uuid() -> Uuid {
+        uuid!("8a4e45f3-2c6e-4e3b-9b1e-9b5d1a1f2d3c")
+    }
+}
+
+impl Player {
+    /// Current look rotation, as (yaw, pitch). Used to carry the camera
+    /// orientation across a hot-reload, since a freshly deserialized
+    /// `Player` always starts at `(0.0, 0.0)`.
+    pub(crate) fn look_angles(&self) -> (f32, f32) {
+        (self.yaw, self.pitch)
+    }
+
+    pub(crate) fn set_look_angles(&mut self, yaw: f32, pitch: f32) {
+        self.yaw = yaw;
+        self.pitch = pitch;
+    }
+}
+
+impl ScriptTrait for Player {
+    fn on_update(&mut self, context: &mut ScriptContext) {
+        let Some(game) = context.plugins.get::<Game>() else {
+            return;
+        };
+        let handler = &game.action_handler;
+
+        let forward = handler.action_value("MoveForward");
+        let strafe = handler.action_value("MoveRight");
+        let look_yaw = handler.action_value("LookYaw");
+        let look_pitch = handler.action_value("LookPitch");
+        let jump_pressed = handler.is_pressed("Jump");
+        let jump = jump_pressed && !self.jump_was_pressed;
+        self.jump_was_pressed = jump_pressed;
+
+        self.yaw -= look_yaw * self.look_sensitivity;
+        self.pitch = (self.pitch - look_pitch * self.look_sensitivity).clamp(-1.5, 1.5);
+
+        let rotation = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), self.yaw);
+
+        if let Some(rigid_body) = context.scene.graph[context.handle].cast_mut::<RigidBody>() {
+            let mut velocity = rotation * Vector3::new(strafe, 0.0, forward) * self.move_speed;
+            velocity.y = rigid_body.lin_vel().y;
+            if jump {
+                velocity.y = self.jump_speed;
+            }
+            rigid_body.set_lin_vel(velocity);
+            rigid_body.local_transform_mut().set_rotation(rotation);
+        }
+
+        if let Some(camera) = context.scene.graph.try_get_mut(self.camera) {
+            camera
+                .local_transform_mut()
+                .set_rotation(UnitQuaternion::from_axis_angle(&Vector3::x_axis(), self.pitch));
+        }
+    }
+}
@@ -0,0 +1,210 @@
+//! Virtual filesystem: resolves every asset path `Game` and the engine's
+//! `ResourceManager` ask for through a priority-ordered mount table, so
+//! `data/scene.rgs` and the textures it references can come from a loose
+//! directory (development), a packed read-only archive bundled with the
+//! build, or - on WASM, where loose files and cross-origin paths are
+//! fragile - an HTTP fetch of that same packed archive.
+//!
+//! This plugs in at the same seam the engine itself uses for disk access:
+//! [`fyrox::resource::io::ResourceIo`]. Installing a [`MountedResourceIo`]
+//! on the `ResourceManager` before anything is loaded makes every loader
+//! (`AsyncSceneLoader`, `TextureLoader`, ...) go through it automatically.
+
+use fyrox::{
+    core::{futures::future::BoxFuture, io::FileError, log::Log},
+    resource::io::ResourceIo,
+};
+use std::{
+    io::{Cursor, Read},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+#[cfg(target_arch = "wasm32")]
+async fn fetch_bytes(url: &str) -> Result<Vec<u8>, String> {
+    use fyrox::core::wasm_bindgen::{JsCast, JsValue};
+    use fyrox::core::wasm_bindgen_futures::JsFuture;
+
+    let window = web_sys::window().ok_or("no window")?;
+    let response: web_sys::Response = JsFuture::from(window.fetch_with_str(url))
+        .await
+        .map_err(|e| format!("{e:?}"))?
+        .dyn_into()
+        .map_err(|_| "fetch() did not resolve to a Response")?;
+    let buffer = JsFuture::from(
+        response
+            .array_buffer()
+            .map_err(|e| format!("{e:?}"))?,
+    )
+    .await
+    .map_err(|e| format!("{e:?}"))?;
+    let array = js_sys::Uint8Array::new(&buffer);
+    let mut bytes = vec![0u8; array.length() as usize];
+    array.copy_to(&mut bytes);
+    let _: JsValue = buffer;
+    Ok(bytes)
+}
+
+/// A single, read-only asset source.
+enum Mount {
+    /// Reads straight from disk under `root`, for local development.
+    LooseDir(PathBuf),
+    /// Reads entries out of a zip archive bundled with the build.
+    Archive(Mutex<zip::ZipArchive<std::fs::File>>),
+    /// Fetches and caches a zip archive over HTTP, for the WASM target
+    /// where loose files and cross-origin requests are awkward.
+    #[cfg(target_arch = "wasm32")]
+    HttpArchive {
+        url: String,
+        cache: Mutex<Option<zip::ZipArchive<Cursor<Vec<u8>>>>>,
+    },
+}
+
+impl Mount {
+    fn loose_dir(root: impl Into<PathBuf>) -> Self {
+        Self::LooseDir(root.into())
+    }
+
+    fn archive(path: &Path) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        let archive = zip::ZipArchive::new(file)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        Ok(Self::Archive(Mutex::new(archive)))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn http_archive(url: impl Into<String>) -> Self {
+        Self::HttpArchive {
+            url: url.into(),
+            cache: Mutex::new(None),
+        }
+    }
+
+    async fn read(&self, path: &Path) -> Option<Vec<u8>> {
+        let key = path.to_string_lossy().replace('\\', "/");
+        match self {
+            Self::LooseDir(root) => fyrox::core::io::load_file(&root.join(path)).await.ok(),
+            Self::Archive(archive) => {
+                let mut archive = archive.lock().unwrap();
+                let mut entry = archive.by_name(&key).ok()?;
+                let mut buf = Vec::with_capacity(entry.size() as usize);
+                entry.read_to_end(&mut buf).ok()?;
+                Some(buf)
+            }
+            #[cfg(target_arch = "wasm32")]
+            Self::HttpArchive { url, cache } => {
+                // The cache is only checked/filled here; the guard is always
+                // dropped before `fetch_bytes(url).await` so we never hold a
+                // `MutexGuard` across an await point.
+                if cache.lock().unwrap().is_none() {
+                    let bytes = fetch_bytes(url).await.ok()?;
+                    let archive = zip::ZipArchive::new(Cursor::new(bytes)).ok()?;
+                    *cache.lock().unwrap() = Some(archive);
+                }
+                let mut cache = cache.lock().unwrap();
+                let archive = cache.as_mut()?;
+                let mut entry = archive.by_name(&key).ok()?;
+                let mut buf = Vec::with_capacity(entry.size() as usize);
+                entry.read_to_end(&mut buf).ok()?;
+                Some(buf)
+            }
+        }
+    }
+
+    async fn exists(&self, path: &Path) -> bool {
+        self.read(path).await.is_some()
+    }
+}
+
+/// Resolves asset paths against a list of [`Mount`]s, highest priority
+/// first, and implements [`ResourceIo`] so it can be installed directly on
+/// a `ResourceManager`.
+pub struct MountedResourceIo {
+    mounts: Vec<Mount>,
+}
+
+impl MountedResourceIo {
+    pub fn builder() -> MountedResourceIoBuilder {
+        MountedResourceIoBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct MountedResourceIoBuilder {
+    mounts: Vec<Mount>,
+}
+
+impl MountedResourceIoBuilder {
+    pub fn with_loose_dir(mut self, root: impl Into<PathBuf>) -> Self {
+        self.mounts.push(Mount::loose_dir(root));
+        self
+    }
+
+    pub fn with_archive(mut self, path: &Path) -> std::io::Result<Self> {
+        self.mounts.push(Mount::archive(path)?);
+        Ok(self)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn with_http_archive(mut self, url: impl Into<String>) -> Self {
+        self.mounts.push(Mount::http_archive(url));
+        self
+    }
+
+    pub fn build(self) -> MountedResourceIo {
+        MountedResourceIo {
+            mounts: self.mounts,
+        }
+    }
+}
+
+impl ResourceIo for MountedResourceIo {
+    fn exists<'a>(&'a self, path: &'a Path) -> BoxFuture<'a, bool> {
+        Box::pin(async move {
+            for mount in &self.mounts {
+                if mount.exists(path).await {
+                    return true;
+                }
+            }
+            false
+        })
+    }
+
+    fn load_file<'a>(&'a self, path: &'a Path) -> BoxFuture<'a, Result<Vec<u8>, FileError>> {
+        Box::pin(async move {
+            for mount in &self.mounts {
+                if let Some(bytes) = mount.read(path).await {
+                    return Ok(bytes);
+                }
+            }
+            Err(FileError::Custom(format!(
+                "{} was not found in any mounted asset source",
+                path.display()
+            )))
+        })
+    }
+}
+
+/// Builds the default mount table for this demo: a packed archive shipped
+/// alongside the build takes priority, falling back to the loose `data`
+/// directory for development; on WASM the archive is fetched over HTTP
+/// instead since it can't be opened as a local file.
+pub fn default_mounts() -> MountedResourceIo {
+    let builder = MountedResourceIo::builder();
+
+    #[cfg(target_arch = "wasm32")]
+    let builder = builder.with_http_archive("data.pak");
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let builder = match builder.with_archive(Path::new("data.pak")) {
+        Ok(builder) => builder,
+        Err(err) => {
+            Log::err(format!(
+                "Failed to open data.pak ({err}), falling back to loose files only."
+            ));
+            MountedResourceIo::builder()
+        }
+    };
+
+    builder.with_loose_dir(".").build()
+}
@@ -1,12 +1,17 @@
 //! Executor with your game connected to it as a plugin.
 use blendshape::GameConstructor;
-use fyrox::core::wasm_bindgen::{self, prelude::*};
-use fyrox::dpi::LogicalSize;
 use fyrox::engine::executor::Executor;
 use fyrox::engine::GraphicsContextParams;
-use fyrox::event_loop::EventLoop;
 use fyrox::window::WindowAttributes;
 
+#[cfg(target_arch = "wasm32")]
+use fyrox::core::wasm_bindgen::{self, prelude::*};
+#[cfg(target_arch = "wasm32")]
+use fyrox::dpi::LogicalSize;
+#[cfg(target_arch = "wasm32")]
+use fyrox::event_loop::EventLoop;
+
+#[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(js_namespace = console)]
@@ -21,6 +26,7 @@ extern "C" {
     fn stack(error: &Error) -> String;
 }
 
+#[cfg(target_arch = "wasm32")]
 fn custom_panic_hook(info: &std::panic::PanicInfo) {
     let mut msg = info.to_string();
     msg.push_str("\n\nStack:\n\n");
@@ -31,6 +37,7 @@ fn custom_panic_hook(info: &std::panic::PanicInfo) {
     error(msg);
 }
 
+#[cfg(target_arch = "wasm32")]
 #[inline]
 pub fn set_panic_hook() {
     use std::sync::Once;
@@ -40,6 +47,7 @@ pub fn set_panic_hook() {
     });
 }
 
+#[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
 pub fn main() {
     set_panic_hook();
@@ -56,4 +64,28 @@ pub fn main() {
     );
     executor.add_plugin_constructor(GameConstructor);
     executor.run()
-}
\ No newline at end of file
+}
+
+/// Native entry point for Android, run alongside the WASM `main` above.
+/// Android has no canvas to size a window against and instead drives
+/// `Suspended`/`Resumed` through the `android_activity` event loop, so the
+/// window starts full-screen and lets the OS own its size.
+#[cfg(target_os = "android")]
+#[no_mangle]
+fn android_main(app: fyrox::event_loop::android::AndroidApp) {
+    use fyrox::event_loop::{android::EventLoopBuilderExtAndroid, EventLoopBuilder};
+
+    let event_loop = EventLoopBuilder::new().with_android_app(app).build().unwrap();
+    let mut executor = Executor::from_params(
+        event_loop,
+        GraphicsContextParams {
+            window_attributes: WindowAttributes {
+                resizable: true,
+                ..Default::default()
+            },
+            vsync: true,
+        },
+    );
+    executor.add_plugin_constructor(GameConstructor);
+    executor.run()
+}